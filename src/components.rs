@@ -0,0 +1,70 @@
+use bevy::prelude::*;
+
+// Consts
+pub const SNAKE_HEAD_COLOR: Color = Color::rgb(0.7, 0.7, 0.7);
+pub const SNAKE_SEGMENT_COLOR: Color = Color::rgb(0.3, 0.3, 0.3);
+
+pub const FOOD_COLOR: Color = Color::rgb(1.0, 0.0, 1.0);
+
+pub const ARENA_WIDTH: u32 = 30;
+pub const ARENA_HEIGHT: u32 = 30;
+
+#[derive(PartialEq, Clone, Copy)]
+pub enum Direction {
+    Left,
+    Up,
+    Right,
+    Down,
+}
+
+impl Direction {
+    pub fn opposite(self) -> Self {
+        match self {
+            Self::Left => Self::Right,
+            Self::Up => Self::Down,
+            Self::Right => Self::Left,
+            Self::Down => Self::Up,
+        }
+    }
+}
+
+#[derive(Component)]
+pub struct SnakeHead {
+    pub direction: Direction,
+    pub intention: Direction,
+}
+
+#[derive(Component)]
+pub struct SnakeSegment;
+
+#[derive(Component)]
+pub struct Food;
+
+#[derive(Component)]
+pub struct ScoreText;
+
+// Shared game-level resource: read by the HUD in `game`, written when the
+// snake eats in `snake`, so it lives here to keep the module edge one-way.
+#[derive(Default, Deref, DerefMut)]
+pub struct Score(pub u32);
+
+#[derive(Component, Clone, Copy, PartialEq, Eq)]
+pub struct Position {
+    pub x: i32,
+    pub y: i32,
+}
+
+#[derive(Component)]
+pub struct Size {
+    pub width: f32,
+    pub height: f32,
+}
+
+impl Size {
+    pub fn square(x: f32) -> Self {
+        Self {
+            width: x,
+            height: x,
+        }
+    }
+}
@@ -0,0 +1,206 @@
+use bevy::{core::FixedTimestep, ecs::schedule::ShouldRun, prelude::*};
+
+use crate::components::*;
+use crate::snake::*;
+
+#[derive(Default, Deref, DerefMut)]
+pub struct HighScore(pub u32);
+
+/// Top-level flow of the game.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum GameState {
+    Menu,
+    Playing,
+    Paused,
+    GameOver,
+}
+
+/// Bundles the whole snake game so it can be dropped into any [`App`].
+pub struct SnakeGamePlugin;
+
+impl Plugin for SnakeGamePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_state(GameState::Menu)
+            .init_resource::<SnakeSegments>()
+            .init_resource::<LastTailPosition>()
+            .init_resource::<FoodCount>()
+            .init_resource::<Score>()
+            .init_resource::<HighScore>()
+            .add_event::<GrowthEvent>()
+            .add_event::<GameOverEvent>()
+            .add_startup_system(setup_camera)
+            .add_startup_system(setup_ui)
+            .add_system(game_state_input)
+            .add_system_set(SystemSet::on_enter(GameState::Playing).with_system(start_game))
+            .add_system_set(
+                SystemSet::on_update(GameState::Playing)
+                    .with_system(
+                        snake_movement_input
+                            .label(SnakeLabel::Input)
+                            .before(SnakeLabel::Movement),
+                    )
+                    .with_system(game_over.after(SnakeLabel::Movement)),
+            )
+            .add_system_set(
+                SystemSet::new()
+                    .with_run_criteria(FixedTimestep::step(0.1).chain(run_if_playing))
+                    .with_system(snake_movement.label(SnakeLabel::Movement))
+                    .with_system(
+                        snake_eating
+                            .label(SnakeLabel::Eating)
+                            .after(SnakeLabel::Movement),
+                    )
+                    .with_system(
+                        snake_growth
+                            .label(SnakeLabel::Growth)
+                            .after(SnakeLabel::Eating),
+                    ),
+            )
+            .add_system_set(
+                SystemSet::new()
+                    .with_run_criteria(FixedTimestep::step(1.0).chain(run_if_playing))
+                    .with_system(food_spawner),
+            )
+            .add_system_set_to_stage(
+                CoreStage::PostUpdate,
+                SystemSet::new()
+                    .with_system(position_translation)
+                    .with_system(size_scaling)
+                    .with_system(score_text_update),
+            );
+    }
+}
+
+pub fn setup_camera(mut commands: Commands) {
+    commands.spawn_bundle(OrthographicCameraBundle::new_2d());
+}
+
+pub fn setup_ui(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.spawn_bundle(UiCameraBundle::default());
+
+    let font = asset_server.load("fonts/DejaVuSans-Bold.ttf");
+    commands
+        .spawn_bundle(TextBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                position: Rect {
+                    top: Val::Px(10.0),
+                    left: Val::Px(10.0),
+                    ..default()
+                },
+                ..default()
+            },
+            text: Text::with_section(
+                "Score: 0  High: 0",
+                TextStyle {
+                    font,
+                    font_size: 30.0,
+                    color: Color::WHITE,
+                },
+                TextAlignment::default(),
+            ),
+            ..default()
+        })
+        .insert(ScoreText);
+}
+
+pub fn score_text_update(
+    score: Res<Score>,
+    high_score: Res<HighScore>,
+    mut q: Query<&mut Text, With<ScoreText>>,
+) {
+    let mut text = q.single_mut();
+    text.sections[0].value = format!("Score: {}  High: {}", score.0, high_score.0);
+}
+
+/// Restricts a fixed-timestep run criteria to the [`GameState::Playing`] state.
+pub fn run_if_playing(In(input): In<ShouldRun>, state: Res<State<GameState>>) -> ShouldRun {
+    match input {
+        ShouldRun::No | ShouldRun::NoAndCheckAgain => ShouldRun::No,
+        other if state.current() == &GameState::Playing => other,
+        _ => ShouldRun::No,
+    }
+}
+
+pub fn game_state_input(keyboard: Res<Input<KeyCode>>, mut state: ResMut<State<GameState>>) {
+    if keyboard.just_pressed(KeyCode::Space) {
+        match state.current() {
+            // Start a fresh board from the menu or after a game over.
+            GameState::Menu | GameState::GameOver => {
+                let _ = state.set(GameState::Playing);
+            }
+            // Pause/resume keep the running board on the state stack.
+            GameState::Playing => {
+                let _ = state.push(GameState::Paused);
+            }
+            GameState::Paused => {
+                let _ = state.pop();
+            }
+        }
+    } else if keyboard.just_pressed(KeyCode::Escape) {
+        match state.current() {
+            GameState::Playing => {
+                let _ = state.push(GameState::Paused);
+            }
+            GameState::Paused => {
+                let _ = state.pop();
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Clears any leftover board and spawns a fresh snake whenever play begins.
+pub fn start_game(
+    mut commands: Commands,
+    mut score: ResMut<Score>,
+    segments_res: ResMut<SnakeSegments>,
+    food: Query<Entity, With<Food>>,
+    segments: Query<Entity, With<SnakeSegment>>,
+) {
+    score.0 = 0;
+    food.iter().chain(segments.iter()).for_each(|e| {
+        commands.entity(e).despawn();
+    });
+    spawn_snake(commands, segments_res);
+}
+
+pub fn game_over(
+    mut reader: EventReader<GameOverEvent>,
+    mut state: ResMut<State<GameState>>,
+    score: Res<Score>,
+    mut high_score: ResMut<HighScore>,
+) {
+    if reader.iter().next().is_some() {
+        high_score.0 = high_score.0.max(score.0);
+        let _ = state.set(GameState::GameOver);
+    }
+}
+
+pub fn size_scaling(windows: Res<Windows>, mut q: Query<(&Size, &mut Transform)>) {
+    let window = windows.get_primary().unwrap();
+
+    for (sprite_size, mut transform) in q.iter_mut() {
+        transform.scale = Vec3::new(
+            sprite_size.width / ARENA_WIDTH as f32 * window.width(),
+            sprite_size.height / ARENA_HEIGHT as f32 * window.height(),
+            1.0,
+        );
+    }
+}
+
+pub fn position_translation(windows: Res<Windows>, mut q: Query<(&Position, &mut Transform)>) {
+    fn convert(pos: f32, bound_window: f32, bound_game: f32) -> f32 {
+        let tile_size = bound_window / bound_game;
+        pos / bound_game * bound_window - (bound_window / 2.) + (tile_size / 2.)
+    }
+
+    let window = windows.get_primary().unwrap();
+    for (pos, mut transform) in q.iter_mut() {
+        transform.translation = Vec3::new(
+            convert(pos.x as f32, window.width(), ARENA_WIDTH as f32),
+            convert(pos.y as f32, window.height(), ARENA_HEIGHT as f32),
+            transform.translation[2],
+        )
+    }
+}
@@ -0,0 +1,217 @@
+use bevy::prelude::*;
+
+use crate::components::*;
+
+// Resources
+#[derive(Default, Deref, DerefMut)]
+pub struct SnakeSegments(pub Vec<Entity>);
+
+#[derive(Default, Deref, DerefMut)]
+pub struct LastTailPosition(pub Option<Position>);
+
+pub struct FoodCount(pub u32);
+
+impl Default for FoodCount {
+    fn default() -> Self {
+        Self(1)
+    }
+}
+
+// Events
+pub struct GrowthEvent;
+
+pub struct GameOverEvent;
+
+/// Ordering of the fixed-timestep movement pipeline.
+#[derive(SystemLabel, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SnakeLabel {
+    Input,
+    Movement,
+    Eating,
+    Growth,
+}
+
+pub fn spawn_snake(mut commands: Commands, mut segments: ResMut<SnakeSegments>) {
+    *segments = SnakeSegments(vec![
+        commands
+            .spawn_bundle(SpriteBundle {
+                sprite: Sprite {
+                    color: SNAKE_HEAD_COLOR,
+                    ..default()
+                },
+                transform: {
+                    let mut t = Transform {
+                        scale: Vec3::new(10.0, 10.0, 10.0),
+                        ..default()
+                    };
+                    t.translation.z = 2.0;
+                    t
+                },
+                ..default()
+            })
+            .insert(SnakeHead {
+                direction: Direction::Right,
+                intention: Direction::Right,
+            })
+            .insert(SnakeSegment)
+            .insert(Position { x: 3, y: 3 })
+            .insert(Size::square(0.8))
+            .id(),
+        spawn_segment(commands, Position { x: 3, y: 2 }),
+    ]);
+}
+
+pub fn snake_movement_input(keyboard_input: Res<Input<KeyCode>>, mut q: Query<&mut SnakeHead>) {
+    let mut head = q.single_mut();
+
+    let dir = if keyboard_input.pressed(KeyCode::Left) {
+        Direction::Left
+    } else if keyboard_input.pressed(KeyCode::Right) {
+        Direction::Right
+    } else if keyboard_input.pressed(KeyCode::Down) {
+        Direction::Down
+    } else if keyboard_input.pressed(KeyCode::Up) {
+        Direction::Up
+    } else {
+        head.direction
+    };
+
+    if dir != head.direction.opposite() {
+        head.intention = dir;
+    }
+}
+
+pub fn snake_movement(
+    segments: ResMut<SnakeSegments>,
+    mut head: Query<(Entity, &mut SnakeHead)>,
+    mut positions: Query<&mut Position, With<SnakeSegment>>,
+    mut last_tail_position: ResMut<LastTailPosition>,
+    mut game_over_writer: EventWriter<GameOverEvent>,
+) {
+    let (head_entity, mut head) = head.single_mut();
+    head.direction = head.intention;
+    let segment_positions = segments
+        .iter()
+        .map(|&e| *positions.get(e).unwrap())
+        .collect::<Vec<_>>();
+    let mut head_position = positions.get_mut(head_entity).unwrap();
+
+    match &head.direction {
+        Direction::Left => {
+            head_position.x -= 1;
+        }
+        Direction::Right => {
+            head_position.x += 1;
+        }
+        Direction::Down => {
+            head_position.y -= 1;
+        }
+        Direction::Up => {
+            head_position.y += 1;
+        }
+    }
+
+    if head_position.x < 0
+        || head_position.y < 0
+        || head_position.x as u32 >= ARENA_WIDTH
+        || head_position.y as u32 >= ARENA_HEIGHT
+        || segment_positions.contains(&head_position)
+    {
+        game_over_writer.send(GameOverEvent);
+    }
+
+    segment_positions
+        .iter()
+        .zip(segments.iter().skip(1))
+        .for_each(|(&pos, &segment)| {
+            *positions.get_mut(segment).unwrap() = pos;
+        });
+
+    *last_tail_position = LastTailPosition(Some(*segment_positions.last().unwrap()));
+}
+
+pub fn snake_eating(
+    mut commands: Commands,
+    mut growth_writer: EventWriter<GrowthEvent>,
+    mut score: ResMut<Score>,
+    food_positions: Query<(Entity, &Position), With<Food>>,
+    head_position: Query<&Position, With<SnakeHead>>,
+) {
+    let head_pos = head_position.single();
+
+    for (ent, food_pos) in food_positions.iter() {
+        if food_pos == head_pos {
+            commands.entity(ent).despawn();
+            growth_writer.send(GrowthEvent);
+            score.0 += 1;
+        }
+    }
+}
+
+pub fn snake_growth(
+    commands: Commands,
+    last_tail_position: Res<LastTailPosition>,
+    mut segments: ResMut<SnakeSegments>,
+    mut growth_reader: EventReader<GrowthEvent>,
+) {
+    if growth_reader.iter().next().is_some() {
+        segments.push(spawn_segment(commands, last_tail_position.0.unwrap()));
+    }
+}
+
+pub fn food_spawner(
+    mut commands: Commands,
+    food_count: Res<FoodCount>,
+    segments: Query<&Position, With<SnakeSegment>>,
+    food: Query<&Position, With<Food>>,
+) {
+    let occupied = segments
+        .iter()
+        .chain(food.iter())
+        .copied()
+        .collect::<Vec<_>>();
+
+    let mut free = (0..ARENA_WIDTH as i32)
+        .flat_map(|x| (0..ARENA_HEIGHT as i32).map(move |y| Position { x, y }))
+        .filter(|pos| !occupied.contains(pos))
+        .collect::<Vec<_>>();
+
+    let missing = food_count.0.saturating_sub(food.iter().count() as u32);
+    for _ in 0..missing {
+        if free.is_empty() {
+            break;
+        }
+        let position = free.swap_remove(fastrand::usize(..free.len()));
+        commands
+            .spawn_bundle(SpriteBundle {
+                sprite: Sprite {
+                    color: FOOD_COLOR,
+                    ..default()
+                },
+                ..default()
+            })
+            .insert(Food)
+            .insert(position)
+            .insert(Size::square(0.8));
+    }
+}
+
+pub fn spawn_segment(mut commands: Commands, position: Position) -> Entity {
+    commands
+        .spawn_bundle(SpriteBundle {
+            sprite: Sprite {
+                color: SNAKE_SEGMENT_COLOR,
+                ..default()
+            },
+            transform: {
+                let mut t = Transform { ..default() };
+                t.translation.z = 2.0;
+                t
+            },
+            ..default()
+        })
+        .insert(SnakeSegment)
+        .insert(position)
+        .insert(Size::square(0.7))
+        .id()
+}